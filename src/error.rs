@@ -0,0 +1,80 @@
+//! The error type returned by this crate's parsing and sending operations.
+
+use std::fmt;
+
+/// The error type for this crate's fallible operations.
+#[derive(Debug)]
+pub enum WolError {
+    /// A MAC address string didn't decode to exactly 6 octets.
+    InvalidLength {
+        /// The number of octets (or, for separator-less input, bytes) that were found.
+        found: usize,
+    },
+    /// One of a MAC address string's groups wasn't valid hex.
+    InvalidHex {
+        /// The offending group, as it appeared in the input.
+        group: String,
+    },
+    /// A SecureOn password wasn't 4 or 6 bytes long.
+    InvalidPasswordLength {
+        /// The number of bytes that were found.
+        found: usize,
+    },
+    /// Sending the magic packet over the network failed.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for WolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WolError::InvalidLength { found } => {
+                write!(f, "invalid MAC address: expected 6 octets, found {}", found)
+            }
+            WolError::InvalidHex { group } => {
+                write!(f, "invalid MAC address: '{}' is not a valid hex octet", group)
+            }
+            WolError::InvalidPasswordLength { found } => {
+                write!(f, "invalid SecureOn password: expected 4 or 6 bytes, found {}", found)
+            }
+            WolError::Io(err) => write!(f, "failed to send magic packet: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for WolError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WolError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for WolError {
+    fn from(err: std::io::Error) -> WolError {
+        WolError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_invalid_length() {
+        let err = WolError::InvalidLength { found: 5 };
+        assert_eq!(err.to_string(), "invalid MAC address: expected 6 octets, found 5");
+    }
+
+    #[test]
+    fn displays_invalid_hex() {
+        let err = WolError::InvalidHex { group: "ZZ".to_string() };
+        assert_eq!(err.to_string(), "invalid MAC address: 'ZZ' is not a valid hex octet");
+    }
+
+    #[test]
+    fn displays_invalid_password_length() {
+        let err = WolError::InvalidPasswordLength { found: 3 };
+        assert_eq!(err.to_string(), "invalid SecureOn password: expected 4 or 6 bytes, found 3");
+    }
+}
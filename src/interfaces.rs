@@ -0,0 +1,151 @@
+//! Enumerating local IPv4 interfaces, so a magic packet can be broadcast out every one of them
+//! instead of whichever single interface the OS would otherwise pick for `255.255.255.255`.
+
+use std::net::Ipv4Addr;
+
+/// A local IPv4 interface along with its directed broadcast address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Ipv4Interface {
+    pub address: Ipv4Addr,
+    pub broadcast: Ipv4Addr,
+}
+
+// The `ifaddrs`/`sockaddr`/`sockaddr_in` layouts below match glibc's <net/if.h>, <ifaddrs.h>,
+// and <netinet/in.h>, which are Linux-specific: other Unix-likes (macOS, the BSDs) prefix both
+// `sockaddr` structs with a `sa_len`/`sin_len` byte and use `u8` rather than `u16` for the family
+// field, so reusing this module there would silently derive wrong addresses instead of failing
+// loudly. We therefore only implement enumeration on Linux and report "unsupported" elsewhere.
+#[cfg(target_os = "linux")]
+pub(crate) fn local_ipv4_interfaces() -> std::io::Result<Vec<Ipv4Interface>> {
+    linux::local_ipv4_interfaces()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn local_ipv4_interfaces() -> std::io::Result<Vec<Ipv4Interface>> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "enumerating network interfaces is only implemented on Linux",
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::Ipv4Interface;
+    use std::io;
+    use std::net::Ipv4Addr;
+    use std::os::raw::{c_char, c_int, c_uint, c_ushort, c_void};
+
+    const AF_INET: c_ushort = 2;
+    const IFF_UP: c_uint = 0x1;
+    const IFF_BROADCAST: c_uint = 0x2;
+    const IFF_LOOPBACK: c_uint = 0x8;
+
+    #[repr(C)]
+    struct sockaddr {
+        sa_family: c_ushort,
+        sa_data: [c_char; 14],
+    }
+
+    #[repr(C)]
+    struct sockaddr_in {
+        sin_family: c_ushort,
+        sin_port: u16,
+        sin_addr: u32,
+        sin_zero: [u8; 8],
+    }
+
+    #[repr(C)]
+    struct ifaddrs {
+        ifa_next: *mut ifaddrs,
+        ifa_name: *mut c_char,
+        ifa_flags: c_uint,
+        ifa_addr: *mut sockaddr,
+        ifa_netmask: *mut sockaddr,
+        ifa_broadaddr: *mut sockaddr,
+        ifa_data: *mut c_void,
+    }
+
+    extern "C" {
+        fn getifaddrs(ifap: *mut *mut ifaddrs) -> c_int;
+        fn freeifaddrs(ifa: *mut ifaddrs);
+    }
+
+    /// Returns `true` for an interface we should send out: up, broadcast-capable, and not
+    /// loopback. Takes the raw flag bitmask so it can be unit-tested without a real `ifaddrs`.
+    fn should_include(flags: c_uint) -> bool {
+        flags & IFF_UP != 0 && flags & IFF_BROADCAST != 0 && flags & IFF_LOOPBACK == 0
+    }
+
+    /// Builds an `Ipv4Addr` from a `sockaddr_in`'s family and (network-byte-order) `sin_addr`,
+    /// or `None` if the family isn't `AF_INET`. Split out from `ipv4_addr` so the byte-order
+    /// conversion can be unit-tested without a real `sockaddr` pointer.
+    fn ipv4_from_sin(sin_family: c_ushort, sin_addr: u32) -> Option<Ipv4Addr> {
+        if sin_family != AF_INET {
+            return None;
+        }
+        Some(Ipv4Addr::from(u32::from_be(sin_addr)))
+    }
+
+    unsafe fn ipv4_addr(addr: *const sockaddr) -> Option<Ipv4Addr> {
+        if addr.is_null() {
+            return None;
+        }
+        let addr_in = &*(addr as *const sockaddr_in);
+        ipv4_from_sin(addr_in.sin_family, addr_in.sin_addr)
+    }
+
+    pub(super) fn local_ipv4_interfaces() -> io::Result<Vec<Ipv4Interface>> {
+        let mut head: *mut ifaddrs = std::ptr::null_mut();
+
+        if unsafe { getifaddrs(&mut head) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut interfaces = Vec::new();
+        let mut entry = head;
+
+        while !entry.is_null() {
+            let ifa = unsafe { &*entry };
+
+            if should_include(ifa.ifa_flags) {
+                let address = unsafe { ipv4_addr(ifa.ifa_addr) };
+                let broadcast = unsafe { ipv4_addr(ifa.ifa_broadaddr) };
+
+                if let (Some(address), Some(broadcast)) = (address, broadcast) {
+                    interfaces.push(Ipv4Interface { address, broadcast });
+                }
+            }
+
+            entry = ifa.ifa_next;
+        }
+
+        unsafe { freeifaddrs(head) };
+
+        Ok(interfaces)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn should_include_requires_up_and_broadcast_but_not_loopback() {
+            assert!(should_include(IFF_UP | IFF_BROADCAST));
+            assert!(!should_include(IFF_BROADCAST));
+            assert!(!should_include(IFF_UP));
+            assert!(!should_include(IFF_UP | IFF_BROADCAST | IFF_LOOPBACK));
+        }
+
+        #[test]
+        fn ipv4_from_sin_converts_network_byte_order() {
+            let sin_addr = u32::from_ne_bytes([192, 0, 2, 1]);
+            assert_eq!(ipv4_from_sin(AF_INET, sin_addr), Some(Ipv4Addr::new(192, 0, 2, 1)));
+        }
+
+        #[test]
+        fn ipv4_from_sin_rejects_non_inet_family() {
+            let sin_addr = u32::from_ne_bytes([192, 0, 2, 1]);
+            assert_eq!(ipv4_from_sin(AF_INET + 1, sin_addr), None);
+        }
+    }
+}
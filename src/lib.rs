@@ -19,120 +19,227 @@
 //! contents of the magic packet, use `magic_bytes()`.
 
 use std::convert::TryFrom;
-use std::convert::TryInto;
 use std::net::{Ipv4Addr, ToSocketAddrs, UdpSocket};
 
+mod error;
+mod interfaces;
+mod mac_addr;
+mod sender;
+pub use error::WolError;
+pub use mac_addr::MacAddr;
+pub use sender::WakeSender;
+
+/// Length of a magic packet with no SecureOn password: 6 header bytes + 16 MAC repetitions.
+const MAGIC_BYTES_LEN: usize = 102;
+
+/// Longest SecureOn password this crate supports (some NICs only accept 4 bytes).
+const MAX_PASSWORD_LEN: usize = 6;
+
+/// Largest payload a `MagicPacket` can hold: the base packet plus the longest SecureOn password.
+const MAX_MAGIC_BYTES_LEN: usize = MAGIC_BYTES_LEN + MAX_PASSWORD_LEN;
+
 /// A Wake-on-LAN magic packet.
 pub struct MagicPacket {
-    magic_bytes: [u8; 102]
+    /// Fixed-capacity buffer holding the packet; only the first `len` bytes are meaningful.
+    magic_bytes: [u8; MAX_MAGIC_BYTES_LEN],
+    len: usize,
 }
 
 impl MagicPacket {
-    
+
     /// Creates a new `MagicPacket` intended for `mac_address` (but doesn't send it yet).
-    pub fn new(mac_address: &[u8; 6]) -> MagicPacket {
-        let mut magic_bytes: [u8; 102];
-        
-        // We use `unsafe` code to skip unnecessary array initialization and bounds checking.
-        unsafe {
-            magic_bytes = std::mem::uninitialized();
-            
-            // Copy the header to the beginning.
-            let mut src: *const u8 = &MAGIC_BYTES_HEADER[0];
-            let mut dst: *mut u8 = &mut magic_bytes[0];
-            dst.copy_from_nonoverlapping(src, 6);
-            
-            // Copy the MAC address once from the argument.
-            src = &mac_address[0];
-            dst = dst.offset(6);
-            dst.copy_from_nonoverlapping(src, 6);
-
-            // Repeat the MAC.
-            let src: *const u8 = dst; // src points to magic_bytes[6]
-            dst = dst.offset(6);
-            dst.copy_from_nonoverlapping(src, 6);
-            
-            dst = dst.offset(6);
-            dst.copy_from_nonoverlapping(src, 12);
-            
-            dst = dst.offset(12);
-            dst.copy_from_nonoverlapping(src, 24);
-            
-            dst = dst.offset(24);
-            dst.copy_from_nonoverlapping(src, 48);
+    pub fn new<T: Into<MacAddr>>(mac_address: T) -> MagicPacket {
+        Self::build(mac_address, &[])
+    }
+
+    /// Creates a new `MagicPacket` intended for `mac_address`, with a SecureOn password appended
+    /// after the MAC repetitions so NICs that gate Wake-on-LAN behind one will accept it.
+    /// `password` must be 4 or 6 bytes long.
+    pub fn with_password<T: Into<MacAddr>>(
+        mac_address: T,
+        password: &[u8],
+    ) -> Result<MagicPacket, WolError> {
+        if password.len() != 4 && password.len() != 6 {
+            return Err(WolError::InvalidPasswordLength { found: password.len() });
         }
-        
-        MagicPacket { magic_bytes }
+
+        Ok(Self::build(mac_address, password))
     }
-    
+
+    /// Builds the packet: the 6-byte `0xFF` header, 16 repetitions of `mac_address`, and finally
+    /// `password` (empty for the password-less case).
+    fn build<T: Into<MacAddr>>(mac_address: T, password: &[u8]) -> MagicPacket {
+        let mac_address = mac_address.into().octets();
+        let mut magic_bytes = [0u8; MAX_MAGIC_BYTES_LEN];
+
+        magic_bytes[..MAGIC_BYTES_HEADER.len()].copy_from_slice(&MAGIC_BYTES_HEADER);
+
+        for repetition in magic_bytes[MAGIC_BYTES_HEADER.len()..MAGIC_BYTES_LEN].chunks_exact_mut(6) {
+            repetition.copy_from_slice(&mac_address);
+        }
+
+        magic_bytes[MAGIC_BYTES_LEN..MAGIC_BYTES_LEN + password.len()].copy_from_slice(password);
+
+        MagicPacket { magic_bytes, len: MAGIC_BYTES_LEN + password.len() }
+    }
+
     /// Sends the magic packet via UDP to the broadcast address `255.255.255.255:9`.
     /// Lets the operating system choose the source port and network interface.
-    pub fn send(&self) -> std::io::Result<()> {
+    pub fn send(&self) -> Result<(), WolError> {
         self.send_to(
             (Ipv4Addr::new(255, 255, 255, 255), 9),
             (Ipv4Addr::new(0, 0, 0, 0), 0)
         )
     }
-    
+
     /// Sends the magic packet via UDP to/from an IP address and port number of your choosing.
-    pub fn send_to<A: ToSocketAddrs>(&self, to_addr: A, from_addr: A) -> std::io::Result<()> {
+    pub fn send_to<A: ToSocketAddrs>(&self, to_addr: A, from_addr: A) -> Result<(), WolError> {
         let socket = UdpSocket::bind(from_addr)?;
         socket.set_broadcast(true)?;
-        socket.send_to(&self.magic_bytes, to_addr)?;
-        
+        socket.send_to(self.magic_bytes(), to_addr)?;
+
         Ok(())
     }
-    
-    /// Returns the magic packet's payload (6 repetitions of `0xFF` and 16 repetitions of the 
-    /// target device's MAC address). Send these bytes yourself over the network if you want to do 
-    /// something more advanced (like reuse a single UDP socket when sending a large number of 
-    /// magic packets).
-    pub fn magic_bytes(&self) -> &[u8; 102] {
-        &self.magic_bytes
+
+    /// Returns the magic packet's payload (6 repetitions of `0xFF`, 16 repetitions of the
+    /// target device's MAC address, and, if set, the SecureOn password). Send these bytes
+    /// yourself over the network if you want to do something more advanced (like reuse a single
+    /// UDP socket when sending a large number of magic packets).
+    pub fn magic_bytes(&self) -> &[u8] {
+        &self.magic_bytes[..self.len]
+    }
+
+    /// Sends the magic packet out every up, non-loopback, broadcast-capable local IPv4
+    /// interface, using that interface's own directed broadcast address rather than
+    /// `255.255.255.255`. On multi-homed hosts this reaches devices that `send()` wouldn't,
+    /// since `255.255.255.255` only goes out whichever single interface the OS happens to pick.
+    /// Returns one result per interface, so callers can tell which sends succeeded.
+    pub fn send_broadcast_all(&self) -> Result<InterfaceSendResults, WolError> {
+        send_to_all_interfaces(self)
     }
 }
 
 const MAGIC_BYTES_HEADER: [u8; 6] = [0xFF; 6];
 
+/// One send outcome per local interface `send_broadcast_all` tried, keyed by that interface's
+/// address.
+pub type InterfaceSendResults = Vec<(Ipv4Addr, Result<(), WolError>)>;
+
+/// Binds one socket per local broadcast-capable IPv4 interface and sends `packet` out each of
+/// them to that interface's directed broadcast address on port 9. Shared by
+/// `MagicPacket::send_broadcast_all` and `WakeSender::send_broadcast_all`.
+pub(crate) fn send_to_all_interfaces(packet: &MagicPacket) -> Result<InterfaceSendResults, WolError> {
+    let interfaces = interfaces::local_ipv4_interfaces()?;
+
+    Ok(send_to_interfaces(packet, interfaces))
+}
+
+/// Does the actual per-interface binding and sending; split out from `send_to_all_interfaces` so
+/// the result plumbing (matching each outcome back to its interface, one bad interface not
+/// aborting the rest) can be unit-tested against a synthetic interface list instead of whatever
+/// interfaces happen to exist on the test machine.
+fn send_to_interfaces(packet: &MagicPacket, interfaces: Vec<interfaces::Ipv4Interface>) -> InterfaceSendResults {
+    interfaces
+        .into_iter()
+        .map(|interface| {
+            let result = (|| -> Result<(), WolError> {
+                let socket = UdpSocket::bind((interface.address, 0))?;
+                socket.set_broadcast(true)?;
+                socket.send_to(packet.magic_bytes(), (interface.broadcast, 9))?;
+                Ok(())
+            })();
+
+            (interface.address, result)
+        })
+        .collect()
+}
+
 impl TryFrom<&str> for MagicPacket {
-    type Error = &'static str;
+    type Error = WolError;
 
     fn try_from(mac_string: &str) -> Result<MagicPacket, Self::Error> {
-        let mac_address = mac_string
-            .split(":")
-            .flat_map(|hex| u8::from_str_radix(hex, 16))
-            .collect::<Vec<u8>>()
-            .try_into()
-            .map_err(|_| "Unable to parse MAC address")?;
+        let mac_address: MacAddr = mac_string.parse()?;
 
-        Ok(Self::new(&mac_address))
+        Ok(Self::new(mac_address))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::convert::TryInto;
+
     #[test]
     fn try_from_valid() {
-        let magic_packet: Result<MagicPacket, &'static str> = "00:11:22:33:44:AA".try_into();
+        let magic_packet: Result<MagicPacket, WolError> = "00:11:22:33:44:AA".try_into();
         assert!(magic_packet.is_ok());
     }
 
     #[test]
     fn try_from_too_short() {
-        let magic_packet: Result<MagicPacket, &'static str> = "00:11:22:33:44".try_into();
+        let magic_packet: Result<MagicPacket, WolError> = "00:11:22:33:44".try_into();
         assert!(magic_packet.is_err());
     }
 
     #[test]
     fn try_from_invalid_u8() {
-        let magic_packet: Result<MagicPacket, &'static str> = "00:11:22:33:44:XX".try_into();
+        let magic_packet: Result<MagicPacket, WolError> = "00:11:22:33:44:XX".try_into();
         assert!(magic_packet.is_err());
     }
 
     #[test]
     fn try_from_lowercase() {
-        let magic_packet: Result<MagicPacket, &'static str> = "00:11:22:33:44:aa".try_into();
+        let magic_packet: Result<MagicPacket, WolError> = "00:11:22:33:44:aa".try_into();
         assert!(magic_packet.is_ok());
     }
+
+    #[test]
+    fn with_password_6_bytes() {
+        let mac = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let packet = MagicPacket::with_password(mac, &[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(packet.magic_bytes().len(), 108);
+        assert_eq!(&packet.magic_bytes()[102..], &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn with_password_4_bytes() {
+        let mac = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let packet = MagicPacket::with_password(mac, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(packet.magic_bytes().len(), 106);
+        assert_eq!(&packet.magic_bytes()[102..], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn with_password_rejects_wrong_length() {
+        let mac = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert!(MagicPacket::with_password(mac, &[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn without_password_is_102_bytes() {
+        let mac = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(MagicPacket::new(mac).magic_bytes().len(), 102);
+    }
+
+    #[test]
+    fn send_to_interfaces_reports_one_result_per_interface() {
+        let packet = MagicPacket::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let bindable = Ipv4Addr::new(127, 0, 0, 1);
+        // TEST-NET-3 (RFC 5737): reserved for documentation, never assigned to a real interface.
+        let unbindable = Ipv4Addr::new(203, 0, 113, 5);
+
+        let results = send_to_interfaces(
+            &packet,
+            vec![
+                interfaces::Ipv4Interface { address: bindable, broadcast: Ipv4Addr::new(127, 0, 0, 2) },
+                interfaces::Ipv4Interface { address: unbindable, broadcast: Ipv4Addr::new(203, 0, 113, 255) },
+            ],
+        );
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, bindable);
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, unbindable);
+        assert!(results[1].1.is_err());
+    }
 }
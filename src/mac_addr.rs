@@ -0,0 +1,281 @@
+//! A dedicated type for 6-byte IEEE 802 MAC addresses.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::WolError;
+
+/// A 6-octet Ethernet MAC address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MacAddr([u8; 6]);
+
+impl MacAddr {
+    /// The broadcast address `ff:ff:ff:ff:ff:ff`.
+    pub const BROADCAST: MacAddr = MacAddr([0xFF; 6]);
+
+    /// The unspecified address `00:00:00:00:00:00`.
+    pub const UNSPECIFIED: MacAddr = MacAddr([0x00; 6]);
+
+    /// Creates a new `MacAddr` from its 6 octets.
+    pub const fn new(octets: [u8; 6]) -> MacAddr {
+        MacAddr(octets)
+    }
+
+    /// Returns the address's 6 octets.
+    pub const fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+
+    /// Returns the 3-octet Organizationally Unique Identifier, i.e. the first half of the
+    /// address.
+    pub const fn oui(&self) -> [u8; 3] {
+        [self.0[0], self.0[1], self.0[2]]
+    }
+
+    /// Returns the 3-octet NIC-specific half of the address, i.e. the second half.
+    pub const fn nic(&self) -> [u8; 3] {
+        [self.0[3], self.0[4], self.0[5]]
+    }
+
+    /// Returns `true` if this is a unicast address, i.e. the least-significant bit of the first
+    /// octet is `0`.
+    pub const fn is_unicast(&self) -> bool {
+        self.0[0] & 0x01 == 0
+    }
+
+    /// Returns `true` if this is a multicast address, i.e. the least-significant bit of the
+    /// first octet is `1`.
+    pub const fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Returns `true` if this address is locally administered rather than globally unique, i.e.
+    /// the second-least-significant bit of the first octet is `1`.
+    pub const fn is_locally_administered(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+
+    /// Derives the modified EUI-64 interface identifier used to build an IPv6 address: the
+    /// address is split in half, `0xFF, 0xFE` is inserted in the middle, and the
+    /// universal/local bit is flipped.
+    pub const fn to_eui64(&self) -> [u8; 8] {
+        let o = self.0;
+        [o[0] ^ 0x02, o[1], o[2], 0xFF, 0xFE, o[3], o[4], o[5]]
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
+        )
+    }
+}
+
+impl MacAddr {
+    /// Parses a MAC address whose 6 octets are separated by `separator`, e.g.
+    /// `MacAddr::from_string("aa-bb-cc-dd-ee-ff", '-')`.
+    ///
+    /// Every octet must be exactly 2 hex digits; anything else (too few/many groups, or a group
+    /// that isn't valid hex) is rejected rather than silently dropped.
+    pub fn from_string(mac_string: &str, separator: char) -> Result<MacAddr, WolError> {
+        let groups: Vec<&str> = mac_string.split(separator).collect();
+        parse_hex_groups(&groups)
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = WolError;
+
+    /// Parses a MAC address in any of the common textual notations: colon-separated
+    /// (`aa:bb:cc:dd:ee:ff`), dash-separated (`aa-bb-cc-dd-ee-ff`), dotted Cisco triplets
+    /// (`aabb.ccdd.eeff`), or 12 hex digits with no separator (`aabbccddeeff`).
+    fn from_str(mac_string: &str) -> Result<MacAddr, Self::Err> {
+        if mac_string.contains(':') {
+            MacAddr::from_string(mac_string, ':')
+        } else if mac_string.contains('-') {
+            MacAddr::from_string(mac_string, '-')
+        } else if mac_string.contains('.') {
+            parse_dotted(mac_string)
+        } else {
+            parse_bare(mac_string)
+        }
+    }
+}
+
+/// Parses `groups`, each expected to be exactly 2 hex digits, into the 6 octets of a `MacAddr`.
+fn parse_hex_groups(groups: &[&str]) -> Result<MacAddr, WolError> {
+    if groups.len() != 6 {
+        return Err(WolError::InvalidLength { found: groups.len() });
+    }
+
+    let mut octets = [0u8; 6];
+    for (octet, group) in octets.iter_mut().zip(groups) {
+        if group.len() != 2 || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(WolError::InvalidHex { group: group.to_string() });
+        }
+        *octet = u8::from_str_radix(group, 16)
+            .map_err(|_| WolError::InvalidHex { group: group.to_string() })?;
+    }
+
+    Ok(MacAddr(octets))
+}
+
+/// Parses dotted Cisco triplets, e.g. `aabb.ccdd.eeff`, where each of the 3 groups is 2 octets.
+fn parse_dotted(mac_string: &str) -> Result<MacAddr, WolError> {
+    let groups: Vec<&str> = mac_string.split('.').collect();
+    if groups.len() != 3 {
+        return Err(WolError::InvalidLength { found: groups.len() * 2 });
+    }
+
+    let mut octets = [0u8; 6];
+    for (i, group) in groups.iter().enumerate() {
+        if group.len() != 4 || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(WolError::InvalidHex { group: group.to_string() });
+        }
+        let value = u16::from_str_radix(group, 16)
+            .map_err(|_| WolError::InvalidHex { group: group.to_string() })?;
+        octets[i * 2] = (value >> 8) as u8;
+        octets[i * 2 + 1] = value as u8;
+    }
+
+    Ok(MacAddr(octets))
+}
+
+/// Parses 12 hex digits with no separator, e.g. `aabbccddeeff`.
+fn parse_bare(mac_string: &str) -> Result<MacAddr, WolError> {
+    if mac_string.len() != 12 {
+        return Err(WolError::InvalidLength { found: mac_string.len() });
+    }
+
+    // Reject non-ASCII up front: byte-slicing below assumes 1 byte == 1 char, which a
+    // multi-byte character (while still making `len()` report 12) would violate and panic on.
+    if !mac_string.is_ascii() {
+        return Err(WolError::InvalidHex { group: mac_string.to_string() });
+    }
+
+    let mut octets = [0u8; 6];
+    for (i, octet) in octets.iter_mut().enumerate() {
+        let group = &mac_string[i * 2..i * 2 + 2];
+        if !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(WolError::InvalidHex { group: group.to_string() });
+        }
+        *octet = u8::from_str_radix(group, 16)
+            .map_err(|_| WolError::InvalidHex { group: group.to_string() })?;
+    }
+
+    Ok(MacAddr(octets))
+}
+
+impl From<[u8; 6]> for MacAddr {
+    fn from(octets: [u8; 6]) -> MacAddr {
+        MacAddr(octets)
+    }
+}
+
+impl From<&[u8; 6]> for MacAddr {
+    fn from(octets: &[u8; 6]) -> MacAddr {
+        MacAddr(*octets)
+    }
+}
+
+impl From<MacAddr> for [u8; 6] {
+    fn from(mac: MacAddr) -> [u8; 6] {
+        mac.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_round_trip() {
+        let mac = MacAddr::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+        assert_eq!(mac.to_string(), "aa:bb:cc:dd:ee:ff");
+        assert_eq!(mac.to_string().parse::<MacAddr>().unwrap(), mac);
+    }
+
+    #[test]
+    fn oui_and_nic_halves() {
+        let mac = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(mac.oui(), [0x00, 0x11, 0x22]);
+        assert_eq!(mac.nic(), [0x33, 0x44, 0x55]);
+    }
+
+    #[test]
+    fn unicast_and_multicast() {
+        let unicast = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert!(unicast.is_unicast());
+        assert!(!unicast.is_multicast());
+
+        let multicast = MacAddr::new([0x01, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert!(multicast.is_multicast());
+        assert!(!multicast.is_unicast());
+    }
+
+    #[test]
+    fn locally_administered() {
+        assert!(!MacAddr::UNSPECIFIED.is_locally_administered());
+        assert!(MacAddr::new([0x02, 0, 0, 0, 0, 0]).is_locally_administered());
+    }
+
+    #[test]
+    fn eui64_derivation() {
+        let mac = MacAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(
+            mac.to_eui64(),
+            [0x02, 0x11, 0x22, 0xFF, 0xFE, 0x33, 0x44, 0x55]
+        );
+    }
+
+    #[test]
+    fn broadcast_and_unspecified_constants() {
+        assert_eq!(MacAddr::BROADCAST.octets(), [0xFF; 6]);
+        assert_eq!(MacAddr::UNSPECIFIED.octets(), [0x00; 6]);
+    }
+
+    #[test]
+    fn parses_dash_separated() {
+        let mac: MacAddr = "aa-bb-cc-dd-ee-ff".parse().unwrap();
+        assert_eq!(mac.octets(), [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn parses_dotted_cisco_triplets() {
+        let mac: MacAddr = "aabb.ccdd.eeff".parse().unwrap();
+        assert_eq!(mac.octets(), [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn parses_bare_hex() {
+        let mac: MacAddr = "aabbccddeeff".parse().unwrap();
+        assert_eq!(mac.octets(), [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn from_string_with_custom_separator() {
+        let mac = MacAddr::from_string("aa/bb/cc/dd/ee/ff", '/').unwrap();
+        assert_eq!(mac.octets(), [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn rejects_invalid_hex_instead_of_dropping_it() {
+        assert!("00:11:22:ZZ:44:55".parse::<MacAddr>().is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_octet_count() {
+        assert!("00:11:22:33:44".parse::<MacAddr>().is_err());
+        assert!("00:11:22:33:44:55:66".parse::<MacAddr>().is_err());
+    }
+
+    #[test]
+    fn rejects_non_ascii_bare_hex_instead_of_panicking() {
+        // `len() == 12` (the multi-byte `\u{e9}` makes up the difference), but it doesn't
+        // char-boundary-align with the 2-byte group slicing `parse_bare` does.
+        assert!("a\u{e9}bccddee11".parse::<MacAddr>().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! A reusable sender for broadcasting many magic packets without re-binding a socket each time.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+
+use crate::{send_to_all_interfaces, InterfaceSendResults, MagicPacket, WolError};
+
+/// Sends `MagicPacket`s by reusing a single bound, broadcast-enabled `UdpSocket`, instead of
+/// paying the cost of `UdpSocket::bind` and `set_broadcast` on every send like `MagicPacket::send_to`
+/// does. Useful when waking a large number of machines at once.
+pub struct WakeSender {
+    socket: UdpSocket,
+}
+
+impl WakeSender {
+    /// Binds a broadcast-enabled UDP socket to `from_addr`, ready to send many magic packets.
+    pub fn new<A: ToSocketAddrs>(from_addr: A) -> Result<WakeSender, WolError> {
+        let socket = UdpSocket::bind(from_addr)?;
+        socket.set_broadcast(true)?;
+
+        Ok(WakeSender { socket })
+    }
+
+    /// Sends `packet` to `to_addr`, reusing the socket bound in `WakeSender::new`.
+    pub fn send<A: ToSocketAddrs>(&self, packet: &MagicPacket, to_addr: A) -> Result<(), WolError> {
+        self.socket.send_to(packet.magic_bytes(), to_addr)?;
+
+        Ok(())
+    }
+
+    /// Sends every packet in `packets` to `to_addr`, reusing the same socket for each one.
+    pub fn send_many<A: ToSocketAddrs + Clone>(
+        &self,
+        packets: &[MagicPacket],
+        to_addr: A,
+    ) -> Vec<Result<(), WolError>> {
+        packets
+            .iter()
+            .map(|packet| self.send(packet, to_addr.clone()))
+            .collect()
+    }
+
+    /// Sends `packet` out every up, non-loopback, broadcast-capable local IPv4 interface, using
+    /// that interface's own directed broadcast address. This binds a fresh socket per interface
+    /// rather than reusing the one from `WakeSender::new`, since reaching every interface
+    /// inherently needs a source address on each of them. Returns one result per interface.
+    pub fn send_broadcast_all(&self, packet: &MagicPacket) -> Result<InterfaceSendResults, WolError> {
+        send_to_all_interfaces(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn send_reaches_a_loopback_receiver() {
+        let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+
+        let sender = WakeSender::new((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let packet = MagicPacket::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        sender.send(&packet, receiver_addr).unwrap();
+
+        let mut buf = [0u8; 102];
+        let (len, _) = receiver.recv_from(&mut buf).unwrap();
+        assert_eq!(&buf[..len], packet.magic_bytes());
+    }
+
+    #[test]
+    fn send_many_reuses_the_socket_for_every_packet() {
+        let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+
+        let sender = WakeSender::new((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let packets = [
+            MagicPacket::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            MagicPacket::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]),
+        ];
+
+        let results = sender.send_many(&packets, receiver_addr);
+        assert!(results.iter().all(Result::is_ok));
+
+        let mut buf = [0u8; 102];
+        for packet in &packets {
+            let (len, from) = receiver.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[..len], packet.magic_bytes());
+            assert_eq!(from, sender.socket.local_addr().unwrap());
+        }
+    }
+}